@@ -6,14 +6,23 @@ use sudoku_wave;
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// File to solve
+    /// File to solve. May hold several puzzles separated by a blank line,
+    /// each of which is solved and printed in turn.
     #[clap(short, long)]
     file: String,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let problem = std::fs::read_to_string(args.file)?;
-    sudoku_wave::solve(&problem)?;
+    let contents = std::fs::read_to_string(args.file)?;
+
+    for (index, problem) in sudoku_wave::split_puzzles(&contents, 9).iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let solved = sudoku_wave::solve::<9>(problem)?;
+        print!("{solved}");
+    }
+
     Ok(())
 }
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+use super::solver::has_conflicting_fixed_cells;
+use super::state::*;
+
+/// Tuning knobs for the Metropolis-Hastings cooling schedule `solve_annealing`
+/// follows: how hot the search starts, how fast it cools, how long it's
+/// willing to run, and how many non-improving moves in a row it tolerates
+/// before reheating to escape a local minimum.
+#[derive(Debug, Clone, Copy)]
+pub struct AnnealingConfig {
+    pub starting_temperature: f64,
+    pub cooling_rate: f64,
+    pub max_iterations: usize,
+    pub reheat_after_stalled_iterations: usize,
+}
+
+impl Default for AnnealingConfig {
+    fn default() -> Self {
+        AnnealingConfig {
+            starting_temperature: 1.0,
+            cooling_rate: 0.999,
+            max_iterations: 200_000,
+            reheat_after_stalled_iterations: 2_000,
+        }
+    }
+}
+
+/// Fills every house with the digits missing from its givens, each in a
+/// random order. The board satisfies the house constraint from this point
+/// on (every later swap trades two cells within the same house), so the
+/// local search below only ever has rows and columns left to fix.
+fn fill_houses(state: &mut Board9) {
+    let block = block_dimension(9);
+    for house_y in (0..9).step_by(block) {
+        for house_x in (0..9).step_by(block) {
+            let house = GameState::<9>::in_house(&Point::new(house_x, house_y));
+            let used: Vec<u16> = house
+                .iter()
+                .filter_map(|point| match state.get(point) {
+                    GameCell::Fixed(value) => Some(value),
+                    GameCell::SuperState(_) => None,
+                })
+                .collect();
+            let mut missing: Vec<u16> = (1..=9).filter(|value| !used.contains(value)).collect();
+            missing.shuffle(&mut thread_rng());
+
+            let blanks: Vec<Point> = house
+                .iter()
+                .copied()
+                .filter(|point| !matches!(state.get(point), GameCell::Fixed(_)))
+                .collect();
+            for point in blanks {
+                *state.get_mut(&point) = GameCell::Fixed(missing.pop().unwrap());
+            }
+        }
+    }
+}
+
+/// How many digits in `unit` are duplicates of another cell in the same
+/// unit, e.g. a value appearing three times counts as two duplicates.
+fn duplicate_count(state: &Board9, unit: &[Point]) -> u32 {
+    let mut counts = [0u32; 10];
+    for point in unit {
+        if let GameCell::Fixed(value) = state.get(point) {
+            counts[value as usize] += 1;
+        }
+    }
+    counts.iter().map(|count| count.saturating_sub(1)).sum()
+}
+
+/// The total number of duplicate digits across every row and column. Houses
+/// are never counted here since `fill_houses` and every later swap keep them
+/// valid by construction. Zero means the board is a solution.
+fn cost(state: &Board9) -> u32 {
+    (0..9)
+        .map(|i| duplicate_count(state, &GameState::<9>::in_row(i)))
+        .chain((0..9).map(|i| duplicate_count(state, &GameState::<9>::in_col(i))))
+        .sum()
+}
+
+fn random_house() -> Vec<Point> {
+    let block = block_dimension(9);
+    let house_x = thread_rng().gen_range(0..3) * block;
+    let house_y = thread_rng().gen_range(0..3) * block;
+    GameState::<9>::in_house(&Point::new(house_x, house_y))
+}
+
+/// A second, local-search solving engine alongside the constraint-propagation
+/// one in [`solve`](super::solver::solve), modeled on the stochastic
+/// optimization approach used by the `optimization_tools` sudoku and the
+/// Worst-Mayor query solver. Runs [`solve_annealing_with`] with the default
+/// [`AnnealingConfig`].
+pub fn solve_annealing(problem: &str) -> Result<Board9> {
+    solve_annealing_with(problem, AnnealingConfig::default())
+}
+
+/// Like [`solve_annealing`], but with an explicit cooling schedule instead of
+/// the default one.
+///
+/// Fills every house with a random permutation of its missing digits, then
+/// repeatedly picks a random house and swaps two of its non-given cells,
+/// accepting the swap outright when it lowers the row/column duplicate
+/// count and otherwise with Metropolis probability
+/// `exp(-delta_cost / temperature)`. `temperature` cools geometrically each
+/// iteration and resets to its starting value if the search stalls at a
+/// nonzero cost for too long. Gives up once `max_iterations` is spent
+/// without reaching cost zero.
+pub fn solve_annealing_with(problem: &str, config: AnnealingConfig) -> Result<Board9> {
+    let mut state: Board9 = GameState::parse(problem)?;
+    if has_conflicting_fixed_cells(&state) {
+        return Err(anyhow!("Puzzle has no solution"));
+    }
+
+    let given: HashSet<Point> = state
+        .cells()
+        .filter(|(_, cell)| matches!(cell, GameCell::Fixed(_)))
+        .map(|(point, _)| point)
+        .collect();
+
+    fill_houses(&mut state);
+
+    let mut temperature = config.starting_temperature;
+    let mut current_cost = cost(&state);
+    let mut stalled = 0;
+
+    for _ in 0..config.max_iterations {
+        if current_cost == 0 {
+            return Ok(state);
+        }
+
+        let swappable: Vec<Point> = random_house()
+            .into_iter()
+            .filter(|point| !given.contains(point))
+            .collect();
+        let mut pair = swappable.choose_multiple(&mut thread_rng(), 2);
+        let a = match pair.next() {
+            Some(point) => *point,
+            None => continue,
+        };
+        let b = match pair.next() {
+            Some(point) => *point,
+            None => continue,
+        };
+
+        let mut attempt = state.clone();
+        let value_a = attempt.get(&a);
+        let value_b = attempt.get(&b);
+        *attempt.get_mut(&a) = value_b;
+        *attempt.get_mut(&b) = value_a;
+
+        let attempt_cost = cost(&attempt);
+        let delta = attempt_cost as f64 - current_cost as f64;
+
+        if delta <= 0.0 || thread_rng().gen::<f64>() < (-delta / temperature).exp() {
+            stalled = if attempt_cost < current_cost {
+                0
+            } else {
+                stalled + 1
+            };
+            state = attempt;
+            current_cost = attempt_cost;
+        } else {
+            stalled += 1;
+        }
+
+        temperature *= config.cooling_rate;
+
+        if current_cost > 0 && stalled >= config.reheat_after_stalled_iterations {
+            temperature = config.starting_temperature;
+            stalled = 0;
+        }
+    }
+
+    if current_cost == 0 {
+        return Ok(state);
+    }
+
+    Err(anyhow!(
+        "Annealing search did not converge within the iteration budget"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_annealing_finds_a_valid_completion() {
+        let problem = "91..8....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+
+        let solved = solve_annealing(problem).unwrap();
+        assert_eq!(cost(&solved), 0);
+
+        let original: Board9 = GameState::parse(problem).unwrap();
+        for (point, cell) in original.cells() {
+            if let GameCell::Fixed(value) = cell {
+                assert_eq!(solved.get(&point), GameCell::Fixed(*value));
+            }
+        }
+    }
+
+    #[test]
+    fn solve_annealing_gives_up_within_its_iteration_budget() {
+        let problem = "91..8....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+
+        let config = AnnealingConfig {
+            max_iterations: 1,
+            ..AnnealingConfig::default()
+        };
+        assert!(solve_annealing_with(problem, config).is_err());
+    }
+
+    #[test]
+    fn solve_annealing_rejects_conflicting_givens_without_spending_iterations() {
+        // Two 9s fixed in the same row can never be satisfied.
+        let problem = "919......
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+.........";
+
+        // The budget-exhausted error would mean the search ran anyway.
+        let err = solve_annealing(problem).unwrap_err();
+        assert_eq!(err.to_string(), "Puzzle has no solution");
+    }
+}
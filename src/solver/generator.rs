@@ -0,0 +1,59 @@
+use rand::{seq::SliceRandom, thread_rng};
+
+use super::grade::{grade, Difficulty};
+use super::solver::{count_solutions, solve};
+use super::state::*;
+
+/// Builds a puzzle with exactly one solution, the way the Hecht solver's
+/// `Generator` does: solve an empty board to get a random full grid, then
+/// dig cells out of it one at a time in random order, keeping a dig only if
+/// the puzzle it leaves behind still has a unique solution and isn't harder
+/// than `difficulty`. Whatever's left once no further cell can be dug out
+/// under those two constraints is the puzzle returned.
+pub fn generate<const SIZE: usize>(difficulty: Difficulty) -> GameState<SIZE> {
+    let empty = (0..SIZE)
+        .map(|_| ".".repeat(SIZE))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut state: GameState<SIZE> = solve(&empty).expect("an empty board always has a solution");
+
+    let mut positions: Vec<Point> = state.cells().map(|(point, _)| point).collect();
+    positions.shuffle(&mut thread_rng());
+
+    for point in positions {
+        let dug = state.get(&point);
+        if !matches!(dug, GameCell::Fixed(_)) {
+            continue;
+        }
+
+        *state.get_mut(&point) = GameCell::default();
+        let problem = state.to_string();
+
+        let unique = count_solutions::<SIZE>(&problem, 2).unwrap_or(0) == 1;
+        let easy_enough = unique && grade::<SIZE>(&problem).is_ok_and(|found| found <= difficulty);
+
+        if !unique || !easy_enough {
+            *state.get_mut(&point) = dug;
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::solver::count_solutions;
+    use super::*;
+
+    #[test]
+    fn generated_puzzle_has_a_unique_solution() {
+        let state: GameState<9> = generate(Difficulty::Easy);
+        assert_eq!(count_solutions::<9>(&state.to_string(), 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn generated_puzzle_is_at_most_as_hard_as_requested() {
+        let state: GameState<9> = generate(Difficulty::Medium);
+        assert!(grade::<9>(&state.to_string()).unwrap() <= Difficulty::Medium);
+    }
+}
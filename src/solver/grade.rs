@@ -0,0 +1,96 @@
+use anyhow::Result;
+
+use super::logic::{deduce, Technique};
+use super::solver::solve_with_guesses;
+use super::state::GameState;
+
+/// How hard a puzzle is to finish, echoing the graded difficulty tiers of
+/// classic solvers rather than a bare solvable/unsolvable result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Trivial,
+    Easy,
+    Medium,
+    Hard,
+    Diabolical,
+}
+
+fn is_solved<const SIZE: usize>(state: &GameState<SIZE>) -> bool {
+    state.cells().all(|(_, cell)| cell.pop_count().is_none())
+}
+
+fn difficulty_for_technique(technique: Technique) -> Difficulty {
+    match technique {
+        Technique::NakedSingle | Technique::HiddenSingle => Difficulty::Easy,
+        Technique::NakedPair
+        | Technique::NakedTriple
+        | Technique::PointingPair
+        | Technique::BoxLineReduction => Difficulty::Medium,
+    }
+}
+
+/// A puzzle that needed a handful of guesses to finish is merely `Hard`;
+/// past this many, the search tree was big enough to call it `Diabolical`.
+const DIABOLICAL_GUESS_THRESHOLD: usize = 4;
+
+/// Grades a puzzle by the hardest technique actually required to solve it:
+/// singles alone are `Trivial`/`Easy`, subsets and pointing pairs bump it to
+/// `Medium`, and anything the deductive engine can't finish on its own falls
+/// back to the backtracking search and is graded `Hard` or `Diabolical` by
+/// how many guesses that search needed.
+pub fn grade<const SIZE: usize>(problem: &str) -> Result<Difficulty> {
+    let mut state: GameState<SIZE> = GameState::parse(problem)?;
+    let steps = deduce(&mut state);
+
+    if is_solved(&state) {
+        let hardest = steps
+            .iter()
+            .map(|step| difficulty_for_technique(step.technique))
+            .max()
+            .unwrap_or(Difficulty::Trivial);
+        return Ok(hardest);
+    }
+
+    let (_, guesses) = solve_with_guesses::<SIZE>(problem)?;
+    Ok(if guesses <= DIABOLICAL_GUESS_THRESHOLD {
+        Difficulty::Hard
+    } else {
+        Difficulty::Diabolical
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grades_a_singles_only_puzzle_as_easy() {
+        let problem = "91..8....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+
+        assert_eq!(grade::<9>(problem).unwrap(), Difficulty::Easy);
+    }
+
+    #[test]
+    fn grades_a_near_empty_puzzle_as_needing_search() {
+        let problem = "..3......
+......1..
+.....6...
+...2.....
+....8....
+.....5...
+...4.....
+..6......
+........9";
+
+        let difficulty = grade::<9>(problem).unwrap();
+        assert!(difficulty >= Difficulty::Hard);
+    }
+}
@@ -0,0 +1,351 @@
+use super::solver::constrain;
+use super::state::*;
+
+fn peers_of<const SIZE: usize>(point: &Point) -> Vec<Point> {
+    let mut points = GameState::<SIZE>::in_row(point.y);
+    points.extend(GameState::<SIZE>::in_col(point.x));
+    points.extend(GameState::<SIZE>::in_house(point));
+    points
+}
+
+/// A human solving technique, in roughly increasing order of difficulty.
+/// [`grade`](super::grade) uses the hardest technique a puzzle actually
+/// needed to bucket it into a [`Difficulty`](super::grade::Difficulty).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    NakedPair,
+    NakedTriple,
+    PointingPair,
+    BoxLineReduction,
+}
+
+/// One deduction the engine made: `technique` removed or placed `value` at
+/// `point`. Callers use these to show their reasoning instead of just a
+/// finished grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    pub point: Point,
+    pub value: u16,
+}
+
+fn all_houses<const SIZE: usize>() -> Vec<Vec<Point>> {
+    let block = block_dimension(SIZE);
+    let mut houses = vec![];
+    for y in (0..SIZE).step_by(block) {
+        for x in (0..SIZE).step_by(block) {
+            houses.push(GameState::<SIZE>::in_house(&Point::new(x, y)));
+        }
+    }
+    houses
+}
+
+fn all_units<const SIZE: usize>() -> Vec<Vec<Point>> {
+    let mut units: Vec<Vec<Point>> = (0..SIZE).map(GameState::<SIZE>::in_row).collect();
+    units.extend((0..SIZE).map(GameState::<SIZE>::in_col));
+    units.extend(all_houses::<SIZE>());
+    units
+}
+
+fn cells_with_candidate<const SIZE: usize>(
+    state: &GameState<SIZE>,
+    unit: &[Point],
+    value: u16,
+) -> Vec<Point> {
+    unit.iter()
+        .copied()
+        .filter(|p| {
+            state
+                .get(p)
+                .potential_values()
+                .is_some_and(|values| values.contains(&value))
+        })
+        .collect()
+}
+
+fn eliminate_mask<const SIZE: usize>(
+    state: &mut GameState<SIZE>,
+    point: Point,
+    mask: u32,
+    technique: Technique,
+    steps: &mut Vec<Step>,
+) {
+    if let GameCell::SuperState(v) = state.get(&point) {
+        let removed = v & mask;
+        if removed != 0 && state.get_mut(&point).eliminate(mask) {
+            for value in 1..=SIZE as u16 {
+                if GameCell::<SIZE>::value_to_bit(value) & removed != 0 {
+                    steps.push(Step {
+                        technique,
+                        point,
+                        value,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn eliminate_value<const SIZE: usize>(
+    state: &mut GameState<SIZE>,
+    point: Point,
+    value: u16,
+    technique: Technique,
+    steps: &mut Vec<Step>,
+) {
+    eliminate_mask(state, point, GameCell::<SIZE>::value_to_bit(value), technique, steps);
+}
+
+/// A `SuperState` down to its last candidate is really a `Fixed` cell that
+/// hasn't been told yet.
+fn apply_naked_singles<const SIZE: usize>(state: &mut GameState<SIZE>) -> Vec<Step> {
+    let mut steps = vec![];
+    let found: Vec<(Point, u16)> = state
+        .cells()
+        .filter(|(_, cell)| cell.pop_count() == Some(1))
+        .map(|(point, cell)| (point, cell.potential_values().unwrap()[0]))
+        .collect();
+
+    for (point, value) in found {
+        *state.get_mut(&point) = GameCell::Fixed(value);
+        constrain(state, &point);
+        steps.push(Step {
+            technique: Technique::NakedSingle,
+            point,
+            value,
+        });
+    }
+    steps
+}
+
+/// A candidate that appears in only one cell of a unit has to go there,
+/// even if that cell still has other candidates left.
+fn apply_hidden_singles<const SIZE: usize>(state: &mut GameState<SIZE>) -> Vec<Step> {
+    let mut steps = vec![];
+    for unit in all_units::<SIZE>() {
+        for value in 1..=SIZE as u16 {
+            let cells = cells_with_candidate(state, &unit, value);
+            if cells.len() == 1 {
+                let point = cells[0];
+                *state.get_mut(&point) = GameCell::Fixed(value);
+                constrain(state, &point);
+                steps.push(Step {
+                    technique: Technique::HiddenSingle,
+                    point,
+                    value,
+                });
+            }
+        }
+    }
+    steps
+}
+
+/// If `size` cells in a unit share an identical `size`-candidate mask, none
+/// of those candidates can live anywhere else in the unit.
+fn apply_naked_subsets<const SIZE: usize>(state: &mut GameState<SIZE>, size: u32) -> Vec<Step> {
+    let technique = match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => unreachable!("naked subsets are only driven for pairs and triples"),
+    };
+
+    let mut steps = vec![];
+    for unit in all_units::<SIZE>() {
+        let mut groups: Vec<(u32, Vec<Point>)> = vec![];
+        for &point in &unit {
+            let cell = state.get(&point);
+            if cell.pop_count() == Some(size) {
+                let mask = cell.bit_pattern();
+                match groups.iter_mut().find(|(m, _)| *m == mask) {
+                    Some((_, members)) => members.push(point),
+                    None => groups.push((mask, vec![point])),
+                }
+            }
+        }
+
+        for (mask, members) in groups {
+            if members.len() as u32 != size {
+                continue;
+            }
+            for &point in &unit {
+                if !members.contains(&point) {
+                    eliminate_mask(state, point, mask, technique, &mut steps);
+                }
+            }
+        }
+    }
+    steps
+}
+
+fn common_row(cells: &[Point]) -> Option<usize> {
+    let y = cells[0].y;
+    cells.iter().all(|p| p.y == y).then_some(y)
+}
+
+fn common_col(cells: &[Point]) -> Option<usize> {
+    let x = cells[0].x;
+    cells.iter().all(|p| p.x == x).then_some(x)
+}
+
+/// Pointing pairs/triples (a candidate confined to one row or column within
+/// a house) and box-line reduction (the same idea run the other way, from a
+/// row or column into the house it points at).
+fn apply_pointing<const SIZE: usize>(state: &mut GameState<SIZE>) -> Vec<Step> {
+    let mut steps = vec![];
+
+    for house in all_houses::<SIZE>() {
+        for value in 1..=SIZE as u16 {
+            let cells = cells_with_candidate(state, &house, value);
+            if cells.len() < 2 {
+                continue;
+            }
+            if let Some(row) = common_row(&cells) {
+                for point in GameState::<SIZE>::in_row(row) {
+                    if !house.contains(&point) {
+                        eliminate_value(state, point, value, Technique::PointingPair, &mut steps);
+                    }
+                }
+            }
+            if let Some(col) = common_col(&cells) {
+                for point in GameState::<SIZE>::in_col(col) {
+                    if !house.contains(&point) {
+                        eliminate_value(state, point, value, Technique::PointingPair, &mut steps);
+                    }
+                }
+            }
+        }
+    }
+
+    let lines = (0..SIZE)
+        .map(GameState::<SIZE>::in_row)
+        .chain((0..SIZE).map(GameState::<SIZE>::in_col));
+    for line in lines {
+        for value in 1..=SIZE as u16 {
+            let cells = cells_with_candidate(state, &line, value);
+            if cells.len() < 2 {
+                continue;
+            }
+            let house = GameState::<SIZE>::in_house(&cells[0]);
+            if cells.iter().all(|p| house.contains(p)) {
+                for point in house {
+                    if !line.contains(&point) {
+                        eliminate_value(state, point, value, Technique::BoxLineReduction, &mut steps);
+                    }
+                }
+            }
+        }
+    }
+
+    steps
+}
+
+/// Parsing a puzzle just drops its givens onto the board; nothing has told
+/// their peers yet. Every technique here assumes candidates already reflect
+/// every fixed cell, so `deduce` primes that once up front. This only
+/// eliminates candidates, never collapses a peer down to `Fixed` itself —
+/// any cell that falls to its last candidate this way is a naked single,
+/// and `apply_naked_singles` is what gets to claim that step.
+fn apply_givens<const SIZE: usize>(state: &mut GameState<SIZE>) {
+    let fixed: Vec<(Point, u16)> = state
+        .cells()
+        .filter_map(|(point, cell)| match cell {
+            GameCell::Fixed(value) => Some((point, *value)),
+            GameCell::SuperState(_) => None,
+        })
+        .collect();
+
+    for (point, value) in fixed {
+        let mask = GameCell::<SIZE>::value_to_bit(value);
+        for peer in peers_of::<SIZE>(&point) {
+            if peer != point {
+                state.get_mut(&peer).eliminate(mask);
+            }
+        }
+    }
+}
+
+/// Applies the techniques, in order of increasing difficulty, to a
+/// fixpoint: as soon as one makes progress the sequence starts over from
+/// the easiest, so a harder technique only runs once every easier one has
+/// stalled. Returns every step taken, in the order they were applied, so a
+/// caller can show its work. A peer that a single's placement leaves with
+/// one candidate is fixed along with it by `constrain` and gets no step of
+/// its own.
+pub fn deduce<const SIZE: usize>(state: &mut GameState<SIZE>) -> Vec<Step> {
+    apply_givens(state);
+
+    let mut steps = vec![];
+    loop {
+        let mut applied = apply_naked_singles(state);
+        if applied.is_empty() {
+            applied = apply_hidden_singles(state);
+        }
+        if applied.is_empty() {
+            applied = apply_naked_subsets(state, 2);
+        }
+        if applied.is_empty() {
+            applied = apply_naked_subsets(state, 3);
+        }
+        if applied.is_empty() {
+            applied = apply_pointing(state);
+        }
+
+        if applied.is_empty() {
+            break;
+        }
+        steps.extend(applied);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn naked_single_fixes_last_candidate() {
+        let mut state: GameState<9> = GameState::default();
+        let point = Point::new(0, 0);
+        *state.get_mut(&point) = GameCell::SuperState(GameCell::<9>::value_to_bit(7));
+
+        let steps = apply_naked_singles(&mut state);
+        assert_eq!(state.get(&point), GameCell::Fixed(7));
+        assert_eq!(
+            steps,
+            vec![Step {
+                technique: Technique::NakedSingle,
+                point,
+                value: 7,
+            }]
+        );
+    }
+
+    #[test]
+    fn deduce_solves_puzzle_with_only_singles() {
+        let problem = "91..8....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+
+        let solution = "915483726
+486279153
+273156948
+397842561
+564391872
+821765394
+742918635
+638527419
+159634287";
+
+        let mut state: GameState<9> = GameState::parse(problem).unwrap();
+        deduce(&mut state);
+        assert_eq!(state.to_string(), format!("{}\n", solution));
+    }
+}
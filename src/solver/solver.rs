@@ -1,65 +1,247 @@
-use anyhow::Result;
-use rand::{prelude::IteratorRandom, thread_rng};
+use std::collections::HashSet;
 
+use anyhow::{anyhow, Result};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    thread_rng,
+};
+
+use super::logic::deduce;
 use super::state::*;
 
-fn lowest_entropy(state: &GameState) -> Option<Point> {
-    let mut cells = state.cells();
-    println!("A: {:?}", state.get(&Point::new(3, 0)).pop_count());
-    println!("B: {:?}", state.cells().count());
-    let min = state
+fn lowest_entropy<const SIZE: usize>(state: &GameState<SIZE>) -> Option<Point> {
+    let min_value = state
         .cells()
-        .min_by_key(|(_, cell)| cell.pop_count().unwrap_or(u32::MAX));
-    if let Some(min) = min {
-        if let Some(min_value) = min.1.pop_count() {
-            let choice = state
-                .cells()
-                .filter(|(_, cell)| cell.pop_count() == Some(min_value))
-                .choose(&mut thread_rng());
-            choice.map(|c| c.0)
-        } else {
-            None
-        }
-    } else {
-        None
-    }
+        .filter_map(|(_, cell)| cell.pop_count())
+        .min()?;
+
+    state
+        .cells()
+        .filter(|(_, cell)| cell.pop_count() == Some(min_value))
+        .choose(&mut thread_rng())
+        .map(|c| c.0)
 }
 
-fn affected_cells(source: &Point) -> Vec<Point> {
+fn affected_cells<const SIZE: usize>(source: &Point) -> Vec<Point> {
     let mut cells = vec![];
-    cells.append(&mut GameState::in_col(source.x));
-    cells.append(&mut GameState::in_row(source.y));
-    cells.append(&mut GameState::in_house(source));
+    cells.append(&mut GameState::<SIZE>::in_col(source.x));
+    cells.append(&mut GameState::<SIZE>::in_row(source.y));
+    cells.append(&mut GameState::<SIZE>::in_house(source));
     cells
 }
 
-fn constrain(state: &mut GameState, source: &Point) {
-    let mut queue = affected_cells(source);
-    let constrained_value = state.get(source);
-    assert!(matches!(constrained_value, GameCell::Fixed(_)));
-    while queue.len() > 0 {
-        let current = queue.pop().unwrap();
-        if state.get_mut(&current).constrain(&constrained_value) {
-            queue.append(&mut affected_cells(&current));
+pub(crate) fn constrain<const SIZE: usize>(state: &mut GameState<SIZE>, source: &Point) {
+    assert!(matches!(state.get(source), GameCell::Fixed(_)));
+    let mut queue = vec![*source];
+    while let Some(current) = queue.pop() {
+        let constrained_value = state.get(&current);
+        for peer in affected_cells::<SIZE>(&current) {
+            if peer == current || !state.get_mut(&peer).constrain(&constrained_value) {
+                continue;
+            }
+
+            // A peer whittled down to its last candidate is really fixed
+            // too, so its own value needs to ripple out to its peers in turn.
+            if state.get(&peer).pop_count() == Some(1) {
+                let value = state.get(&peer).potential_values().unwrap()[0];
+                *state.get_mut(&peer) = GameCell::Fixed(value);
+                queue.push(peer);
+            }
         }
     }
 }
 
-pub fn solve(problem: &str) -> Result<GameState> {
+/// True if collapsing `point` to `value` would immediately clash with a
+/// peer that is already `Fixed` to the same value. `constrain` only reports
+/// a contradiction once a `SuperState` has been whittled down to zero
+/// candidates, so this catches the simpler case where the guess itself was
+/// never valid to begin with.
+fn conflicts_with_peer<const SIZE: usize>(
+    state: &GameState<SIZE>,
+    point: &Point,
+    value: u16,
+) -> bool {
+    affected_cells::<SIZE>(point)
+        .iter()
+        .any(|peer| matches!(state.get(peer), GameCell::Fixed(v) if v == value))
+}
+
+fn has_contradiction<const SIZE: usize>(state: &GameState<SIZE>) -> bool {
+    state.cells().any(|(_, cell)| cell.pop_count() == Some(0))
+}
+
+/// True if two peers are both `Fixed` to the same value. Covers two cases
+/// `has_contradiction` can't see: a pair of conflicting givens the puzzle
+/// started with, and a pair of cells `constrain` independently whittled down
+/// to the same forced value without ever comparing them against each other.
+pub(crate) fn has_conflicting_fixed_cells<const SIZE: usize>(state: &GameState<SIZE>) -> bool {
+    state.cells().any(|(point, cell)| {
+        matches!(cell, GameCell::Fixed(value) if affected_cells::<SIZE>(&point)
+            .iter()
+            .any(|peer| *peer != point && matches!(state.get(peer), GameCell::Fixed(v) if v == *value)))
+    })
+}
+
+/// One level of the depth-first search: the board as it stood when `point`
+/// was chosen as the next cell to collapse, and the candidates for that
+/// point still left to try (shuffled so repeated solves don't always guess
+/// in the same order).
+struct Frame<const SIZE: usize> {
+    state: GameState<SIZE>,
+    point: Point,
+    candidates: Vec<u16>,
+}
+
+fn frame_for<const SIZE: usize>(state: GameState<SIZE>, point: Point) -> Frame<SIZE> {
+    let mut candidates = state.get(&point).potential_values().unwrap_or_default();
+    candidates.shuffle(&mut thread_rng());
+    Frame {
+        state,
+        point,
+        candidates,
+    }
+}
+
+pub fn solve<const SIZE: usize>(problem: &str) -> Result<GameState<SIZE>> {
+    solve_with_guesses(problem).map(|(state, _)| state)
+}
+
+/// Like [`solve`], but also reports how many guesses the backtracking search
+/// committed to (including the ones it had to abandon) before it landed on a
+/// solution. [`grade`](super::grade::grade) uses this to tell a puzzle that
+/// needed one lucky guess apart from one that ground through a large search
+/// tree.
+pub fn solve_with_guesses<const SIZE: usize>(problem: &str) -> Result<(GameState<SIZE>, usize)> {
     let mut state = GameState::parse(problem)?;
-    loop {
-        let lowest = lowest_entropy(&state);
-        if let Some(lowest) = lowest {
-            println!("Chosen Spot: {:?}", lowest);
-            let chosen_value = state.get(&lowest).random_potential().unwrap();
-            println!("Chosen Value: {:?}", chosen_value);
-            *state.get_mut(&lowest) = GameCell::Fixed(chosen_value);
-            constrain(&mut state, &lowest);
-        } else {
-            break;
+    if has_conflicting_fixed_cells(&state) {
+        return Err(anyhow!("Puzzle has no solution"));
+    }
+
+    // Narrowing candidates with the deductive engine first, the same way
+    // `count_solutions` does, shrinks the search tree the backtracking below
+    // has to walk before it resorts to guessing.
+    deduce(&mut state);
+    if has_contradiction(&state) || has_conflicting_fixed_cells(&state) {
+        return Err(anyhow!("Puzzle has no solution"));
+    }
+
+    let first_point = match lowest_entropy(&state) {
+        Some(point) => point,
+        None => return Ok((state, 0)),
+    };
+
+    // Boards we've already tried (and failed from) so identical dead states
+    // reached via a different guess order get pruned instead of re-expanded.
+    let mut visited = HashSet::new();
+    visited.insert(state.fingerprint());
+
+    let mut stack = vec![frame_for(state, first_point)];
+    let mut guesses = 0;
+
+    while let Some(frame) = stack.last_mut() {
+        let point = frame.point;
+        let candidate = match frame.candidates.pop() {
+            Some(value) => value,
+            None => {
+                // Exhausted every candidate for this cell: back up a level.
+                stack.pop();
+                continue;
+            }
+        };
+
+        if conflicts_with_peer(&frame.state, &point, candidate) {
+            continue;
+        }
+
+        let mut attempt = frame.state.clone();
+        *attempt.get_mut(&point) = GameCell::Fixed(candidate);
+        constrain(&mut attempt, &point);
+        deduce(&mut attempt);
+        guesses += 1;
+
+        if has_contradiction(&attempt)
+            || has_conflicting_fixed_cells(&attempt)
+            || !visited.insert(attempt.fingerprint())
+        {
+            continue;
+        }
+
+        match lowest_entropy(&attempt) {
+            None => return Ok((attempt, guesses)),
+            Some(next_point) => stack.push(frame_for(attempt, next_point)),
+        }
+    }
+
+    Err(anyhow!("Puzzle has no solution"))
+}
+
+/// Counts distinct completions of `problem`, stopping as soon as `cap` are
+/// found instead of exhausting the whole search tree. [`generate`](super::generator::generate)
+/// calls this with a `cap` of 2 after every candidate cell removal: it only
+/// needs to tell "exactly one" from "more than one", not the true count.
+pub fn count_solutions<const SIZE: usize>(problem: &str, cap: usize) -> Result<usize> {
+    let mut state: GameState<SIZE> = GameState::parse(problem)?;
+    if cap == 0 || has_conflicting_fixed_cells(&state) {
+        return Ok(0);
+    }
+
+    // Narrowing candidates with the deductive engine first, the same way
+    // `grade` does, shrinks the search tree the backtracking below has to
+    // walk to tell "exactly one solution" from "more than one".
+    deduce(&mut state);
+    if has_contradiction(&state) || has_conflicting_fixed_cells(&state) {
+        return Ok(0);
+    }
+
+    let first_point = match lowest_entropy(&state) {
+        Some(point) => point,
+        None => return Ok(1),
+    };
+
+    let mut visited = HashSet::new();
+    visited.insert(state.fingerprint());
+
+    let mut stack = vec![frame_for(state, first_point)];
+    let mut found = 0;
+
+    while found < cap {
+        let frame = match stack.last_mut() {
+            Some(frame) => frame,
+            None => break,
+        };
+
+        let point = frame.point;
+        let candidate = match frame.candidates.pop() {
+            Some(value) => value,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+
+        if conflicts_with_peer(&frame.state, &point, candidate) {
+            continue;
+        }
+
+        let mut attempt = frame.state.clone();
+        *attempt.get_mut(&point) = GameCell::Fixed(candidate);
+        constrain(&mut attempt, &point);
+        deduce(&mut attempt);
+
+        if has_contradiction(&attempt)
+            || has_conflicting_fixed_cells(&attempt)
+            || !visited.insert(attempt.fingerprint())
+        {
+            continue;
+        }
+
+        match lowest_entropy(&attempt) {
+            None => found += 1,
+            Some(next_point) => stack.push(frame_for(attempt, next_point)),
         }
     }
-    Ok(state)
+
+    Ok(found)
 }
 
 #[cfg(test)]
@@ -88,6 +270,87 @@ mod tests {
 638527419
 159634287";
 
-        assert_eq!(solve(&problem).unwrap().to_string(), solution);
+        let solved: GameState<9> = solve(&problem).unwrap();
+        assert_eq!(solved.to_string(), format!("{}\n", solution));
+    }
+
+    #[test]
+    fn solve_requiring_backtracking() {
+        // Sparse enough that constraint propagation alone stalls before the
+        // grid is full, forcing the solver to guess and, for some guesses,
+        // backtrack out of a dead end before it finds the real solution.
+        let problem = "..3......
+......1..
+.....6...
+...2.....
+....8....
+.....5...
+...4.....
+..6......
+........9";
+
+        assert!(solve::<9>(&problem).is_ok());
+    }
+
+    #[test]
+    fn unsolvable_problem_returns_err_instead_of_panicking() {
+        // Two 9s fixed in the same row can never be satisfied.
+        let problem = "919......
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+.........";
+
+        assert!(solve::<9>(&problem).is_err());
+    }
+
+    #[test]
+    fn count_solutions_of_a_solved_puzzle_is_one() {
+        let problem = "915483726
+486279153
+273156948
+397842561
+564391872
+821765394
+742918635
+638527419
+159634287";
+
+        assert_eq!(count_solutions::<9>(&problem, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn count_solutions_stops_early_at_cap() {
+        // An empty board has far more than two completions.
+        let problem = ".........
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+.........";
+
+        assert_eq!(count_solutions::<9>(&problem, 2).unwrap(), 2);
+    }
+
+    #[test]
+    fn count_solutions_of_conflicting_givens_is_zero() {
+        let problem = "919......
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+.........";
+
+        assert_eq!(count_solutions::<9>(&problem, 2).unwrap(), 0);
     }
 }
@@ -3,29 +3,50 @@ use std::fmt::{Display, Write};
 use anyhow::{anyhow, Result};
 use rand::{prelude::SliceRandom, thread_rng};
 
+/// A bitmask with every candidate for a `SIZE`-cell board set. `u32` has
+/// room to spare for every standard square size this crate supports
+/// (4×4 through 25×25 need at most 25 bits), so rather than threading a
+/// second "backing integer" type parameter through every board size we just
+/// always use `u32` and let the unused high bits sit at zero.
+pub const fn all_possibilities(size: usize) -> u32 {
+    if size >= 32 {
+        u32::MAX
+    } else {
+        (1 << size) - 1
+    }
+}
+
+/// The board's side length (9 for classic Sudoku, 16, 25, ...) written as a
+/// const generic so `GameCell`/`GameState` are a single generic type rather
+/// than copy-pasted per size.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum GameCell {
-    SuperState(u16),
+pub enum GameCell<const SIZE: usize> {
+    SuperState(u32),
     Fixed(u16),
 }
 
-impl Default for GameCell {
+impl<const SIZE: usize> Default for GameCell<SIZE> {
     fn default() -> Self {
-        GameCell::SuperState(ALL_CELL_POSSIBILITIES)
+        GameCell::SuperState(all_possibilities(SIZE))
     }
 }
 
-impl Display for GameCell {
+impl<const SIZE: usize> Display for GameCell<SIZE> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GameCell::SuperState(_) => f.write_char('.')?,
-            GameCell::Fixed(v) => f.write_str(&v.to_string())?,
+            // Mirror `parse`'s `to_digit(36)`: a single base-36 digit, so
+            // values above 9 (16x16, 25x25 boards) print as one character
+            // ('a'..'p') instead of overflowing the fixed-width grid.
+            GameCell::Fixed(v) => {
+                f.write_char(std::char::from_digit(*v as u32, 36).ok_or(std::fmt::Error)?)?
+            }
         }
         Ok(())
     }
 }
 
-impl GameCell {
+impl<const SIZE: usize> GameCell<SIZE> {
     pub fn pop_count(&self) -> Option<u32> {
         match self {
             GameCell::SuperState(v) => Some(v.count_ones()),
@@ -33,33 +54,21 @@ impl GameCell {
         }
     }
 
-    fn value_to_bit(value: u16) -> u16 {
-        match value {
-            1 => 0b00000000_00000001,
-            2 => 0b00000000_00000010,
-            3 => 0b00000000_00000100,
-            4 => 0b00000000_00001000,
-            5 => 0b00000000_00010000,
-            6 => 0b00000000_00100000,
-            7 => 0b00000000_01000000,
-            8 => 0b00000000_10000000,
-            9 => 0b00000001_00000000,
-            _ => panic!("Invalid value in value_to_bit"),
-        }
+    pub(crate) fn value_to_bit(value: u16) -> u32 {
+        1u32 << (value - 1)
+    }
+
+    fn unset_bit_pattern(value: u16) -> u32 {
+        all_possibilities(SIZE) & !GameCell::<SIZE>::value_to_bit(value)
     }
 
-    fn unset_bit_pattern(value: u16) -> u16 {
-        match value {
-            1 => 0b00000001_11111110,
-            2 => 0b00000001_11111101,
-            3 => 0b00000001_11111011,
-            4 => 0b00000001_11110111,
-            5 => 0b00000001_11101111,
-            6 => 0b00000001_11011111,
-            7 => 0b00000001_10111111,
-            8 => 0b00000001_01111111,
-            9 => 0b00000000_11111111,
-            _ => panic!("Invalid value in value_to_bit"),
+    /// The raw candidate/value bitmask for this cell, regardless of whether
+    /// it has collapsed to `Fixed` yet. Used to fingerprint a board for the
+    /// backtracking search's visited-state cache.
+    pub(crate) fn bit_pattern(&self) -> u32 {
+        match self {
+            GameCell::SuperState(v) => *v,
+            GameCell::Fixed(v) => GameCell::<SIZE>::value_to_bit(*v),
         }
     }
 
@@ -67,8 +76,8 @@ impl GameCell {
         match self {
             GameCell::SuperState(v) => {
                 let mut values = vec![];
-                for i in 1..=9 {
-                    let mask = GameCell::value_to_bit(i);
+                for i in 1..=SIZE as u16 {
+                    let mask = GameCell::<SIZE>::value_to_bit(i);
                     if mask & *v == mask {
                         values.push(i);
                     }
@@ -84,12 +93,12 @@ impl GameCell {
             .map(|v| *v.choose(&mut thread_rng()).unwrap())
     }
 
-    pub fn constrain(&mut self, cell: &GameCell) -> bool {
+    pub fn constrain(&mut self, cell: &GameCell<SIZE>) -> bool {
         if let GameCell::Fixed(constraint) = cell {
             match self {
                 GameCell::SuperState(v) => {
                     let initial = *v;
-                    *v = *v & GameCell::unset_bit_pattern(*constraint);
+                    *v &= GameCell::<SIZE>::unset_bit_pattern(*constraint);
                     initial != *v
                 }
                 GameCell::Fixed(_) => false,
@@ -98,9 +107,24 @@ impl GameCell {
             false
         }
     }
+
+    /// Clears every candidate bit set in `mask` from a `SuperState`. Unlike
+    /// `constrain`, which removes a single peer's fixed value, this lets the
+    /// deductive solver drop several candidates at once (naked subsets,
+    /// pointing pairs). Returns whether any bit actually changed.
+    pub(crate) fn eliminate(&mut self, mask: u32) -> bool {
+        match self {
+            GameCell::SuperState(v) => {
+                let initial = *v;
+                *v &= !mask;
+                initial != *v
+            }
+            GameCell::Fixed(_) => false,
+        }
+    }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: usize,
     pub y: usize,
@@ -112,21 +136,37 @@ impl Point {
     }
 }
 
-pub const ALL_CELL_POSSIBILITIES: u16 = 0b00000001_11111111;
+/// Classic 9×9 Sudoku's candidate mask, kept around for call sites and tests
+/// that only ever deal with the standard board.
+pub const ALL_CELL_POSSIBILITIES: u32 = 0b00000001_11111111;
 
-pub struct GameState {
-    cells: [[GameCell; 9]; 9],
+/// The side length of a house/block for a `size`×`size` board, e.g. 3 for
+/// classic 9×9 Sudoku or 4 for 16×16. Only perfect-square sizes are
+/// supported, matching every size this crate's callers actually use.
+pub(crate) fn block_dimension(size: usize) -> usize {
+    (size as f64).sqrt().round() as usize
 }
 
-impl Default for GameState {
+#[derive(Clone)]
+pub struct GameState<const SIZE: usize> {
+    cells: [[GameCell<SIZE>; SIZE]; SIZE],
+}
+
+/// Handy aliases for the standard square Sudoku variants.
+pub type Board4 = GameState<4>;
+pub type Board9 = GameState<9>;
+pub type Board16 = GameState<16>;
+pub type Board25 = GameState<25>;
+
+impl<const SIZE: usize> Default for GameState<SIZE> {
     fn default() -> Self {
         GameState {
-            cells: [[GameCell::default(); 9]; 9],
+            cells: [[GameCell::default(); SIZE]; SIZE],
         }
     }
 }
 
-impl Display for GameState {
+impl<const SIZE: usize> Display for GameState<SIZE> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for line in self.cells.iter() {
             for value in line.iter() {
@@ -138,88 +178,194 @@ impl Display for GameState {
     }
 }
 
-impl GameState {
-    pub fn get(&self, pos: &Point) -> GameCell {
+impl<const SIZE: usize> GameState<SIZE> {
+    pub fn get(&self, pos: &Point) -> GameCell<SIZE> {
         self.cells[pos.y][pos.x]
     }
 
-    pub fn get_mut(&mut self, pos: &Point) -> &mut GameCell {
+    pub fn get_mut(&mut self, pos: &Point) -> &mut GameCell<SIZE> {
         &mut self.cells[pos.y][pos.x]
     }
 
     pub fn in_row(row: usize) -> Vec<Point> {
-        let mut points = vec![];
-        for i in 0..9 {
-            points.push(Point::new(i, row));
-        }
-        points
+        (0..SIZE).map(|i| Point::new(i, row)).collect()
     }
 
     pub fn in_col(col: usize) -> Vec<Point> {
-        let mut points = vec![];
-        for i in 0..9 {
-            points.push(Point::new(col, i));
-        }
-        points
+        (0..SIZE).map(|i| Point::new(col, i)).collect()
     }
 
     pub fn in_house(pos: &Point) -> Vec<Point> {
-        fn point_to_house_coord(x: usize) -> usize {
-            match x {
-                0..=2 => 0,
-                3..=5 => 1,
-                6..=8 => 2,
-                _ => panic!("Invalid position in in_house"),
-            }
-        }
+        let block = block_dimension(SIZE);
 
-        let house_x = point_to_house_coord(pos.x);
-        let house_y = point_to_house_coord(pos.y);
+        let house_x = pos.x / block;
+        let house_y = pos.y / block;
 
-        let start_house_x = house_x * 3;
-        let start_house_y = house_y * 3;
+        let start_house_x = house_x * block;
+        let start_house_y = house_y * block;
 
         let mut points = vec![];
-        for i in 0..3 {
-            for j in 0..3 {
+        for i in 0..block {
+            for j in 0..block {
                 points.push(Point::new(start_house_x + j, start_house_y + i));
             }
         }
         points
     }
 
-    pub fn cells(&self) -> impl Iterator<Item = (Point, &GameCell)> + '_ {
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &GameCell<SIZE>)> + '_ {
         self.cells
             .iter()
             .enumerate()
-            .map(move |(i, r)| {
+            .map(move |(y, r)| {
                 r.iter()
                     .enumerate()
-                    .map(move |(j, r)| (Point::new(i, j), r))
+                    .map(move |(x, r)| (Point::new(x, y), r))
             })
             .flatten()
     }
 
-    pub fn parse(problem: &str) -> Result<GameState> {
+    /// A compact snapshot of every cell's bit pattern, used by the
+    /// backtracking search to recognize a board it has already explored
+    /// (and failed from) so it can prune the branch instead of re-expanding
+    /// it.
+    pub fn fingerprint(&self) -> [[u32; SIZE]; SIZE] {
+        let mut grid = [[0u32; SIZE]; SIZE];
+        for (point, cell) in self.cells() {
+            grid[point.y][point.x] = cell.bit_pattern();
+        }
+        grid
+    }
+
+    /// Parses a puzzle, tolerant of the common interchange formats seen in
+    /// the wild: a grid spread across `SIZE` lines, or the whole thing
+    /// packed onto a single `SIZE * SIZE`-character line; `0` treated as a
+    /// blank the same as `.`; and interior whitespace (block-separator
+    /// spaces within a row, block-separator blank lines between rows, as in
+    /// the `optimization_tools` grouped layout) stripped out before the grid
+    /// is read. Boards past SIZE 9 also accept each cell written as a
+    /// whitespace-separated decimal number (`"1 2 3 ... 16"` per row)
+    /// instead of packing every cell into a single base-36 character.
+    pub fn parse(problem: &str) -> Result<GameState<SIZE>> {
         let mut state = GameState::default();
 
-        if problem.lines().count() != 9 {
-            return Err(anyhow!("Incorrect input number of lines"));
-        }
-        for (line_index, line) in problem.lines().enumerate() {
-            if line.len() != 9 {
-                return Err(anyhow!("Incorrect line length"));
-            }
-            for (char_index, char) in line.chars().enumerate() {
-                if let Some(value) = char.to_digit(10) {
-                    state.cells[line_index][char_index] = GameCell::Fixed(value as u16);
-                } else if char != '.' {
-                    return Err(anyhow!("Invalid character input"))?;
+        let trimmed_lines: Vec<&str> = problem
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+
+        let values: Vec<u16> = if trimmed_lines.len() == SIZE
+            && trimmed_lines
+                .iter()
+                .all(|line| line.split_whitespace().count() == SIZE)
+        {
+            trimmed_lines
+                .iter()
+                .flat_map(|line| line.split_whitespace())
+                .map(Self::parse_value_token)
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            let lines: Vec<String> = trimmed_lines
+                .iter()
+                .map(|line| line.chars().filter(|c| !c.is_whitespace()).collect())
+                .filter(|line: &String| !line.is_empty())
+                .collect();
+
+            let flattened = if lines.len() == 1 {
+                lines.into_iter().next().unwrap()
+            } else {
+                if lines.len() != SIZE {
+                    return Err(anyhow!("Incorrect input number of lines"));
+                }
+                if lines.iter().any(|line| line.chars().count() != SIZE) {
+                    return Err(anyhow!("Incorrect line length"));
                 }
+                lines.concat()
+            };
+
+            if flattened.chars().count() != SIZE * SIZE {
+                return Err(anyhow!("Incorrect input length"));
+            }
+
+            flattened
+                .chars()
+                .map(Self::parse_value_char)
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        for (index, value) in values.into_iter().enumerate() {
+            if value != 0 {
+                state.cells[index / SIZE][index % SIZE] = GameCell::Fixed(value);
             }
         }
         Ok(state)
     }
+
+    /// A single cell's value out of the packed/hex-digit layouts: `.`/`0`
+    /// for blank, otherwise one base-36 digit (`1`-`9`, then `a`-`p` for
+    /// 10-25).
+    fn parse_value_char(char: char) -> Result<u16> {
+        if char == '.' || char == '0' {
+            return Ok(0);
+        }
+        char.to_digit(36)
+            .filter(|value| *value >= 1 && *value as usize <= SIZE)
+            .map(|value| value as u16)
+            .ok_or_else(|| anyhow!("Invalid character input"))
+    }
+
+    /// A single cell's value out of the whitespace-separated-number layout:
+    /// `.`/`0` for blank, otherwise a plain decimal number.
+    fn parse_value_token(token: &str) -> Result<u16> {
+        if token == "." || token == "0" {
+            return Ok(0);
+        }
+        token
+            .parse::<u16>()
+            .ok()
+            .filter(|value| *value >= 1 && *value as usize <= SIZE)
+            .ok_or_else(|| anyhow!("Invalid character input"))
+    }
+}
+
+/// Splits a file that may hold several `size`×`size` puzzles back-to-back
+/// into the substrings [`parse`](GameState::parse) expects for each one.
+///
+/// A naive `split("\n\n")` would also fire on the blank lines `parse`
+/// tolerates *inside* a single puzzle (the `optimization_tools`
+/// block-separated layout), shredding one puzzle into unparseable
+/// fragments. Instead this only treats a blank line as a puzzle boundary
+/// once the puzzle being accumulated already has a full `size` rows (or,
+/// for the single-line format, is already a complete `size * size`
+/// character line) — the same blank lines `parse` skips over mid-grid are
+/// skipped here too.
+pub fn split_puzzles(contents: &str, size: usize) -> Vec<String> {
+    let mut puzzles = vec![];
+    let mut rows: Vec<&str> = vec![];
+
+    let row_is_complete = |rows: &[&str]| {
+        rows.len() == size
+            || rows
+                .first()
+                .is_some_and(|row| row.chars().filter(|c| !c.is_whitespace()).count() == size * size)
+    };
+
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            if row_is_complete(&rows) {
+                puzzles.push(rows.join("\n"));
+                rows.clear();
+            }
+            continue;
+        }
+        rows.push(line);
+    }
+    if !rows.is_empty() {
+        puzzles.push(rows.join("\n"));
+    }
+
+    puzzles
 }
 
 #[cfg(test)]
@@ -240,7 +386,7 @@ mod tests {
 ...527..9
 ....3..87";
 
-        let state = GameState::parse(&problem).unwrap();
+        let state: GameState<9> = GameState::parse(problem).unwrap();
         assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(9));
         assert_eq!(state.get(&Point::new(0, 1)), GameCell::Fixed(4));
         assert_eq!(state.get(&Point::new(1, 0)), GameCell::Fixed(1));
@@ -248,11 +394,11 @@ mod tests {
         assert_eq!(state.get(&Point::new(0, 3)), GameCell::Fixed(3));
         assert_eq!(
             state.get(&Point::new(1, 1)),
-            GameCell::SuperState(ALL_CELL_POSSIBILITIES)
+            GameCell::SuperState(all_possibilities(9))
         );
         assert_eq!(
             state.get(&Point::new(8, 2)),
-            GameCell::SuperState(ALL_CELL_POSSIBILITIES)
+            GameCell::SuperState(all_possibilities(9))
         );
     }
 
@@ -268,7 +414,7 @@ mod tests {
 ...527..9
 ....3..87";
 
-        let state = GameState::parse(&problem).unwrap();
+        let state: GameState<9> = GameState::parse(problem).unwrap();
         assert_eq!(state.to_string(), format!("{}\n", problem));
     }
 
@@ -284,12 +430,12 @@ mod tests {
 ...527..9
 ....3..87";
 
-        let state = GameState::parse(&problem).unwrap();
+        let state: GameState<9> = GameState::parse(problem).unwrap();
         assert_eq!(
-            GameState::in_row(1)
+            GameState::<9>::in_row(1)
                 .iter()
                 .map(|p| state.get(p))
-                .collect::<Vec<GameCell>>(),
+                .collect::<Vec<GameCell<9>>>(),
             vec!(
                 GameCell::Fixed(4),
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
@@ -303,10 +449,10 @@ mod tests {
             )
         );
         assert_eq!(
-            GameState::in_col(2)
+            GameState::<9>::in_col(2)
                 .iter()
                 .map(|p| state.get(p))
-                .collect::<Vec<GameCell>>(),
+                .collect::<Vec<GameCell<9>>>(),
             vec!(
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
@@ -320,10 +466,10 @@ mod tests {
             )
         );
         assert_eq!(
-            GameState::in_house(&Point::new(0, 2))
+            GameState::<9>::in_house(&Point::new(0, 2))
                 .iter()
                 .map(|p| state.get(p))
-                .collect::<Vec<GameCell>>(),
+                .collect::<Vec<GameCell<9>>>(),
             vec!(
                 GameCell::Fixed(9),
                 GameCell::Fixed(1),
@@ -337,10 +483,10 @@ mod tests {
             )
         );
         assert_eq!(
-            GameState::in_house(&Point::new(8, 4))
+            GameState::<9>::in_house(&Point::new(8, 4))
                 .iter()
                 .map(|p| state.get(p))
-                .collect::<Vec<GameCell>>(),
+                .collect::<Vec<GameCell<9>>>(),
             vec!(
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
@@ -354,10 +500,10 @@ mod tests {
             )
         );
         assert_eq!(
-            GameState::in_house(&Point::new(0, 8))
+            GameState::<9>::in_house(&Point::new(0, 8))
                 .iter()
                 .map(|p| state.get(p))
-                .collect::<Vec<GameCell>>(),
+                .collect::<Vec<GameCell<9>>>(),
             vec!(
                 GameCell::SuperState(ALL_CELL_POSSIBILITIES),
                 GameCell::Fixed(4),
@@ -375,27 +521,27 @@ mod tests {
     #[test]
     fn pop_count() {
         assert_eq!(
-            GameCell::SuperState(ALL_CELL_POSSIBILITIES).pop_count(),
+            GameCell::<9>::SuperState(ALL_CELL_POSSIBILITIES).pop_count(),
             Some(9)
         );
         assert_eq!(
-            GameCell::SuperState(0b00000001_10101010).pop_count(),
+            GameCell::<9>::SuperState(0b00000001_10101010).pop_count(),
             Some(5)
         );
-        assert_eq!(GameCell::Fixed(4).pop_count(), None);
+        assert_eq!(GameCell::<9>::Fixed(4).pop_count(), None);
     }
 
     #[test]
     fn constrain() {
-        let mut cell = GameCell::SuperState(ALL_CELL_POSSIBILITIES);
+        let mut cell = GameCell::<9>::SuperState(ALL_CELL_POSSIBILITIES);
         cell.constrain(&GameCell::SuperState(ALL_CELL_POSSIBILITIES));
         assert_eq!(cell, GameCell::SuperState(ALL_CELL_POSSIBILITIES));
 
-        let mut cell = GameCell::SuperState(ALL_CELL_POSSIBILITIES);
+        let mut cell = GameCell::<9>::SuperState(ALL_CELL_POSSIBILITIES);
         cell.constrain(&GameCell::Fixed(2));
         assert_eq!(cell, GameCell::SuperState(0b00000001_11111101));
 
-        let mut cell = GameCell::SuperState(0b00000001_11111101);
+        let mut cell = GameCell::<9>::SuperState(0b00000001_11111101);
         cell.constrain(&GameCell::Fixed(4));
         assert_eq!(cell, GameCell::SuperState(0b00000001_11110101));
     }
@@ -403,22 +549,22 @@ mod tests {
     #[test]
     fn potential_values() {
         assert_eq!(
-            GameCell::SuperState(ALL_CELL_POSSIBILITIES).potential_values(),
+            GameCell::<9>::SuperState(ALL_CELL_POSSIBILITIES).potential_values(),
             Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 9])
         );
         assert_eq!(
-            GameCell::SuperState(0b00000001_11111101).potential_values(),
+            GameCell::<9>::SuperState(0b00000001_11111101).potential_values(),
             Some(vec![1, 3, 4, 5, 6, 7, 8, 9])
         );
         assert_eq!(
-            GameCell::SuperState(0b00000000_00000001).potential_values(),
+            GameCell::<9>::SuperState(0b00000000_00000001).potential_values(),
             Some(vec![1])
         );
         assert_eq!(
-            GameCell::SuperState(0b00000001_00000000).potential_values(),
+            GameCell::<9>::SuperState(0b00000001_00000000).potential_values(),
             Some(vec![9])
         );
-        assert_eq!(GameCell::Fixed(4).potential_values(), None);
+        assert_eq!(GameCell::<9>::Fixed(4).potential_values(), None);
     }
 
     #[test]
@@ -426,7 +572,7 @@ mod tests {
         let expected: HashSet<u16> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9].iter().copied().collect();
         for _ in 0..100 {
             assert!(expected.contains(
-                &GameCell::SuperState(ALL_CELL_POSSIBILITIES)
+                &GameCell::<9>::SuperState(ALL_CELL_POSSIBILITIES)
                     .random_potential()
                     .unwrap()
             ));
@@ -435,7 +581,7 @@ mod tests {
         let expected: HashSet<u16> = vec![8, 9].iter().copied().collect();
         for _ in 0..100 {
             assert!(expected.contains(
-                &GameCell::SuperState(0b00000001_10000000)
+                &GameCell::<9>::SuperState(0b00000001_10000000)
                     .random_potential()
                     .unwrap()
             ));
@@ -444,13 +590,13 @@ mod tests {
         let expected: HashSet<u16> = vec![9].iter().copied().collect();
         for _ in 0..100 {
             assert!(expected.contains(
-                &GameCell::SuperState(0b00000001_00000000)
+                &GameCell::<9>::SuperState(0b00000001_00000000)
                     .random_potential()
                     .unwrap()
             ));
         }
 
-        assert_eq!(None, GameCell::Fixed(2).random_potential());
+        assert_eq!(None, GameCell::<9>::Fixed(2).random_potential());
     }
 
     #[test]
@@ -465,8 +611,8 @@ mod tests {
 ...527..9
 ....3..87";
 
-        let state = GameState::parse(&problem).unwrap();
-        let solution: Vec<(Point, GameCell)> = state
+        let state: GameState<9> = GameState::parse(problem).unwrap();
+        let solution: Vec<(Point, GameCell<9>)> = state
             .cells()
             .take(5)
             .map(|(pos, cell)| (pos, *cell))
@@ -475,18 +621,164 @@ mod tests {
             solution,
             vec![
                 (Point::new(0, 0), GameCell::Fixed(9)),
-                (Point::new(0, 1), GameCell::Fixed(1)),
-                (
-                    Point::new(0, 2),
-                    GameCell::SuperState(ALL_CELL_POSSIBILITIES)
-                ),
-                (
-                    Point::new(0, 3),
-                    GameCell::SuperState(ALL_CELL_POSSIBILITIES)
-                ),
-                (Point::new(0, 4), GameCell::Fixed(8)),
+                (Point::new(1, 0), GameCell::Fixed(1)),
+                (Point::new(2, 0), GameCell::SuperState(ALL_CELL_POSSIBILITIES)),
+                (Point::new(3, 0), GameCell::SuperState(ALL_CELL_POSSIBILITIES)),
+                (Point::new(4, 0), GameCell::Fixed(8)),
             ]
         );
         assert_eq!(state.cells().count(), 81);
     }
+
+    #[test]
+    fn parses_zero_as_a_blank_cell() {
+        // Same as the `parse` test's puzzle, but the `8` given in the first
+        // row is replaced by a `0`, which must read as a blank.
+        let problem = "91..0....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+
+        let state: GameState<9> = GameState::parse(problem).unwrap();
+        assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(9));
+        assert_eq!(
+            state.get(&Point::new(4, 0)),
+            GameCell::SuperState(all_possibilities(9))
+        );
+    }
+
+    #[test]
+    fn parses_a_single_81_character_line() {
+        let problem =
+            "91..8....4..279....73....4.3...4...15..3.1..28...6...4.4....63....527..9....3..87";
+        let state: GameState<9> = GameState::parse(problem).unwrap();
+        assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(9));
+        assert_eq!(state.get(&Point::new(1, 0)), GameCell::Fixed(1));
+        assert_eq!(
+            state.get(&Point::new(0, 8)),
+            GameCell::SuperState(all_possibilities(9))
+        );
+    }
+
+    #[test]
+    fn parses_a_block_separated_layout() {
+        // The `optimization_tools` `from_string` layout: blocks of rows
+        // separated by blank lines, blocks of columns separated by spaces.
+        let problem = "91. .8. ...
+4.. 279 ...
+.73 ... .4.
+
+3.. .4. ..1
+5.. 3.1 ..2
+8.. .6. ..4
+
+.4. ... 63.
+... 527 ..9
+... .3. .87";
+
+        let state: GameState<9> = GameState::parse(problem).unwrap();
+        assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(9));
+        assert_eq!(state.get(&Point::new(1, 0)), GameCell::Fixed(1));
+        assert_eq!(state.get(&Point::new(0, 3)), GameCell::Fixed(3));
+    }
+
+    #[test]
+    fn parses_a_16x16_board() {
+        let mut rows = vec!["1".to_string() + &".".repeat(15)];
+        rows.extend(std::iter::repeat_n(".".repeat(16), 15));
+        let problem = rows.join("\n");
+
+        let state: GameState<16> = GameState::parse(&problem).unwrap();
+        assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(1));
+        assert_eq!(
+            state.get(&Point::new(1, 0)),
+            GameCell::SuperState(all_possibilities(16))
+        );
+        assert_eq!(GameState::<16>::in_house(&Point::new(0, 0)).len(), 16);
+    }
+
+    #[test]
+    fn parses_a_16x16_board_with_space_separated_numbers() {
+        // Some interchange files write each cell as a plain decimal number,
+        // a row per line with cells separated by spaces, rather than packed
+        // into a single base-36 character.
+        let mut rows = vec!["1 . . . . . . . . . . 12 . . . .".to_string()];
+        rows.extend(std::iter::repeat_n(
+            std::iter::repeat_n(".", 16).collect::<Vec<_>>().join(" "),
+            15,
+        ));
+        let problem = rows.join("\n");
+
+        let state: GameState<16> = GameState::parse(&problem).unwrap();
+        assert_eq!(state.get(&Point::new(0, 0)), GameCell::Fixed(1));
+        assert_eq!(state.get(&Point::new(11, 0)), GameCell::Fixed(12));
+        assert_eq!(
+            state.get(&Point::new(1, 0)),
+            GameCell::SuperState(all_possibilities(16))
+        );
+    }
+
+    #[test]
+    fn displays_and_round_trips_values_above_nine() {
+        // 16x16 boards need values 10-16, which must print as a single
+        // base-36 digit ('a'..'g') to stay parseable.
+        let mut rows = vec!["123456789abcdefg".to_string()];
+        rows.extend(std::iter::repeat_n(".".repeat(16), 15));
+        let problem = rows.join("\n");
+
+        let state: GameState<16> = GameState::parse(&problem).unwrap();
+        assert_eq!(state.get(&Point::new(15, 0)), GameCell::Fixed(16));
+        assert_eq!(state.to_string(), format!("{}\n", problem));
+
+        let round_tripped: GameState<16> = GameState::parse(&state.to_string()).unwrap();
+        assert_eq!(round_tripped.get(&Point::new(15, 0)), GameCell::Fixed(16));
+    }
+
+    #[test]
+    fn split_puzzles_does_not_shred_a_block_separated_single_puzzle() {
+        // A lone puzzle in the `optimization_tools` layout has blank lines
+        // between its row-blocks; a naive split("\n\n") would cut it into
+        // three unparseable 3-line fragments.
+        let problem = "91. .8. ...
+4.. 279 ...
+.73 ... .4.
+
+3.. .4. ..1
+5.. 3.1 ..2
+8.. .6. ..4
+
+.4. ... 63.
+... 527 ..9
+... .3. .87";
+
+        let puzzles = split_puzzles(problem, 9);
+        assert_eq!(puzzles.len(), 1);
+        assert!(GameState::<9>::parse(&puzzles[0]).is_ok());
+    }
+
+    #[test]
+    fn split_puzzles_splits_several_puzzles_in_one_file() {
+        let first = "91..8....
+4..279...
+.73....4.
+3...4...1
+5..3.1..2
+8...6...4
+.4....63.
+...527..9
+....3..87";
+        let second =
+            "91..8....4..279....73....4.3...4...15..3.1..28...6...4.4....63....527..9....3..87";
+
+        let contents = format!("{first}\n\n{second}");
+        let puzzles = split_puzzles(&contents, 9);
+        assert_eq!(puzzles.len(), 2);
+        assert!(GameState::<9>::parse(&puzzles[0]).is_ok());
+        assert!(GameState::<9>::parse(&puzzles[1]).is_ok());
+    }
 }